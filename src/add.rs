@@ -0,0 +1,113 @@
+//! Interactive `add` command: prompts for the entry's parameters, optionally
+//! generating a fresh secret, and hands back a ready-to-store `otpauth://`
+//! URI. Validation mirrors what [`crate::parse_totp`] accepts on the way in.
+
+use data_encoding::BASE32_NOPAD;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::io::{self, Write};
+use totp_rs::{Algorithm, TOTP};
+
+use crate::{generate_code, OtpKind};
+
+fn prompt_line(label: &str) -> io::Result<String> {
+    print!("{}", label);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+fn prompt_algorithm() -> io::Result<Algorithm> {
+    loop {
+        let input = prompt_line("Algorithm [SHA1/SHA256/SHA512] (default SHA1): ")?;
+        match input.to_uppercase().as_str() {
+            "" | "SHA1" => return Ok(Algorithm::SHA1),
+            "SHA256" => return Ok(Algorithm::SHA256),
+            "SHA512" => return Ok(Algorithm::SHA512),
+            other => println!("Unknown algorithm '{}', try again.", other),
+        }
+    }
+}
+
+fn prompt_digits() -> io::Result<u32> {
+    loop {
+        let input = prompt_line("Digits [6/7/8] (default 6): ")?;
+        if input.is_empty() {
+            return Ok(6);
+        }
+        match input.parse::<u32>() {
+            Ok(d) if (6..=8).contains(&d) => return Ok(d),
+            _ => println!("Digits must be 6, 7, or 8."),
+        }
+    }
+}
+
+fn prompt_period() -> io::Result<u64> {
+    loop {
+        let input = prompt_line("Period in seconds (default 30): ")?;
+        if input.is_empty() {
+            return Ok(30);
+        }
+        match input.parse::<u64>() {
+            Ok(p) if p > 0 => return Ok(p),
+            _ => println!("Period must be a positive number of seconds."),
+        }
+    }
+}
+
+/// Generate a cryptographically random 160-bit Base32 secret.
+fn generate_secret() -> (String, Vec<u8>) {
+    let mut bytes = [0u8; 20];
+    OsRng.fill_bytes(&mut bytes);
+    (BASE32_NOPAD.encode(&bytes), bytes.to_vec())
+}
+
+/// Run the interactive prompts and build the `otpauth://` URI for the new
+/// entry, printing the generated secret and a one-shot code for
+/// confirmation. Does not touch the secrets file; the caller appends it.
+pub fn run() -> io::Result<String> {
+    let issuer = prompt_line("Issuer: ")?;
+    let account = prompt_line("Account: ")?;
+    let algorithm = prompt_algorithm()?;
+    let digits = prompt_digits()?;
+    let period = prompt_period()?;
+
+    let secret_input = prompt_line("Secret (Base32, leave blank to generate one): ")?;
+    let (secret_b32, secret_bytes) = if secret_input.is_empty() {
+        generate_secret()
+    } else {
+        let bytes = BASE32_NOPAD
+            .decode(secret_input.to_uppercase().as_bytes())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "secret is not valid Base32"))?;
+        (secret_input.to_uppercase(), bytes)
+    };
+
+    let algorithm_name = match algorithm {
+        Algorithm::SHA1 => "SHA1",
+        Algorithm::SHA256 => "SHA256",
+        Algorithm::SHA512 => "SHA512",
+    };
+    let label = if issuer.is_empty() {
+        account.clone()
+    } else {
+        format!("{}:{}", issuer, account)
+    };
+    let uri = format!(
+        "otpauth://totp/{}?secret={}&issuer={}&algorithm={}&digits={}&period={}",
+        urlencoding::encode(&label),
+        secret_b32,
+        urlencoding::encode(&issuer),
+        algorithm_name,
+        digits,
+        period,
+    );
+
+    let totp = TOTP::new_unchecked(algorithm, digits as usize, 1, period, secret_bytes, Some(issuer), account);
+    let code = generate_code(&totp, OtpKind::Totp { is_steam: false });
+
+    println!("Secret: {}", secret_b32);
+    println!("Current code: {}", code);
+
+    Ok(uri)
+}
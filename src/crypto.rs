@@ -0,0 +1,124 @@
+//! At-rest encryption for the secrets file.
+//!
+//! Layout: `MAGIC (4) | VERSION (1) | salt (16) | nonce (24) | ciphertext`.
+//! The key is derived from the user's passphrase with Argon2id over the
+//! random salt, and the plaintext blob is sealed with XChaCha20-Poly1305
+//! using the random nonce. A failed auth tag check (wrong passphrase or a
+//! corrupted file) surfaces as a plain `io::Error` so callers can report it
+//! and exit rather than silently returning garbage.
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, OsRng};
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+use std::io;
+
+const MAGIC: &[u8; 4] = b"ATU1";
+const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = MAGIC.len() + 1 + SALT_LEN + NONCE_LEN;
+
+/// True if `data` starts with the encrypted secrets file magic.
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.len() >= MAGIC.len() && &data[..MAGIC.len()] == MAGIC
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> io::Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("key derivation failed: {e}")))?;
+    Ok(key)
+}
+
+/// Encrypt `plaintext` under `passphrase`, returning the full on-disk blob
+/// (header followed by ciphertext).
+pub fn encrypt(passphrase: &str, plaintext: &[u8]) -> io::Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let cipher = XChaCha20Poly1305::new((&key).into());
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| io::Error::other(format!("encryption failed: {e}")))?;
+
+    let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&salt);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a blob previously produced by [`encrypt`]. Fails cleanly (no
+/// partial output) on a bad passphrase, a corrupted file, or an
+/// unrecognized version.
+pub fn decrypt(passphrase: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+    if data.len() < HEADER_LEN || !is_encrypted(data) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not an encrypted secrets file"));
+    }
+    let version = data[MAGIC.len()];
+    if version != VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported secrets file version {version}"),
+        ));
+    }
+
+    let salt_start = MAGIC.len() + 1;
+    let nonce_start = salt_start + SALT_LEN;
+    let ciphertext_start = nonce_start + NONCE_LEN;
+
+    let salt = &data[salt_start..nonce_start];
+    let nonce_bytes = &data[nonce_start..ciphertext_start];
+    let ciphertext = &data[ciphertext_start..];
+
+    let key = derive_key(passphrase, salt)?;
+    let cipher = XChaCha20Poly1305::new((&key).into());
+    let nonce = XNonce::from_slice(nonce_bytes);
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "incorrect passphrase or corrupted secrets file"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_encrypt_and_decrypt() {
+        let blob = encrypt("correct horse", b"otpauth://totp/example").unwrap();
+        assert!(is_encrypted(&blob));
+        let plain = decrypt("correct horse", &blob).unwrap();
+        assert_eq!(plain, b"otpauth://totp/example");
+    }
+
+    #[test]
+    fn rejects_wrong_passphrase() {
+        let blob = encrypt("correct horse", b"otpauth://totp/example").unwrap();
+        assert!(decrypt("battery staple", &blob).is_err());
+    }
+
+    #[test]
+    fn rejects_corrupted_ciphertext() {
+        let mut blob = encrypt("correct horse", b"otpauth://totp/example").unwrap();
+        let last = blob.len() - 1;
+        blob[last] ^= 0xFF;
+        assert!(decrypt("correct horse", &blob).is_err());
+    }
+
+    #[test]
+    fn rejects_plaintext_as_encrypted() {
+        let plain = b"otpauth://totp/example".to_vec();
+        assert!(!is_encrypted(&plain));
+        assert!(decrypt("correct horse", &plain).is_err());
+    }
+}
@@ -1,11 +1,31 @@
 use clap::{Parser, Subcommand};
 use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
 use std::fs;
-use std::io::{self, Write};
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
 use totp_rs::{Algorithm, TOTP};
 use url::Url;
 
+mod add;
+mod crypto;
+mod qr;
+mod tui;
+
+/// Alphabet used by Steam's non-standard 5-character TOTP codes.
+const STEAM_ALPHABET: &[u8] = b"23456789BCDFGHJKMNPQRTVWXY";
+
+/// Which flavor of one-time password an entry is. Both TOTP (time-based,
+/// including the Steam variant) and HOTP (counter-based) are stored as
+/// `otpauth://` URIs in the same secrets file.
+#[derive(Clone, Copy)]
+pub(crate) enum OtpKind {
+    Totp { is_steam: bool },
+    Hotp { counter: u64 },
+}
+
 #[derive(Parser)]
 #[command(name = "auth-tui")]
 #[command(about = "Simple TOTP authenticator")]
@@ -30,6 +50,20 @@ enum Command {
         /// Path to write the URIs
         path: String,
     },
+    /// Encrypt an existing plaintext secrets file in place
+    Migrate,
+    /// Render a stored entry as a scannable QR code
+    Qr {
+        /// 1-based index into the secrets file, or an issuer/account substring
+        selector: String,
+    },
+    /// Decode a QR code image and import the resulting otpauth:// URI
+    Scan {
+        /// Path to the QR code image file
+        image: String,
+    },
+    /// Interactively create a new entry, optionally generating its secret
+    Add,
 }
 
 fn default_secrets_path() -> String {
@@ -38,26 +72,86 @@ fn default_secrets_path() -> String {
         .unwrap_or_else(|| ".auth-tui".to_string())
 }
 
-fn load_secrets(path: &str) -> Vec<String> {
-    fs::read_to_string(path)
-        .unwrap_or_default()
-        .lines()
+fn uri_lines(blob: &str) -> Vec<String> {
+    blob.lines()
         .filter(|l| l.starts_with("otpauth://"))
         .map(String::from)
         .collect()
 }
 
-fn save_secrets(path: &str, secrets: &[String]) -> io::Result<()> {
-    fs::write(path, secrets.join("\n"))
+fn prompt_passphrase(prompt: &str) -> io::Result<String> {
+    rpassword::prompt_password(prompt)
+}
+
+fn prompt_new_passphrase() -> io::Result<String> {
+    let first = prompt_passphrase("Set a passphrase to encrypt your secrets file: ")?;
+    let second = prompt_passphrase("Confirm passphrase: ")?;
+    if first != second {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "passphrases did not match"));
+    }
+    Ok(first)
+}
+
+/// Read a plain text file of `otpauth://` URIs, e.g. an import source file.
+/// Unlike [`load_secrets_store`] this never treats the file as our encrypted store.
+fn load_secrets(path: &str) -> Vec<String> {
+    uri_lines(&fs::read_to_string(path).unwrap_or_default())
+}
+
+/// Load the secrets store at `path`, decrypting it if needed. Returns the
+/// entries together with the passphrase used, if any, so a later
+/// [`save_secrets`] call in the same invocation can reuse it instead of
+/// prompting twice.
+fn load_secrets_store(path: &str) -> (Vec<String>, Option<String>) {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(_) => return (Vec::new(), None),
+    };
+    if data.is_empty() {
+        return (Vec::new(), None);
+    }
+
+    if crypto::is_encrypted(&data) {
+        let passphrase = prompt_passphrase("Passphrase: ").unwrap_or_else(|e| {
+            eprintln!("Failed to read passphrase: {}", e);
+            std::process::exit(1);
+        });
+        match crypto::decrypt(&passphrase, &data) {
+            Ok(plain) => (uri_lines(&String::from_utf8_lossy(&plain)), Some(passphrase)),
+            Err(e) => {
+                eprintln!("Failed to decrypt secrets file: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        (uri_lines(&String::from_utf8_lossy(&data)), None)
+    }
+}
+
+/// Encrypt and write the secrets store. If `passphrase` is `None` (no
+/// passphrase was recovered from a prior load, i.e. this is the first save),
+/// the user is prompted to set one.
+pub(crate) fn save_secrets(path: &str, secrets: &[String], passphrase: Option<&str>) -> io::Result<()> {
+    let owned;
+    let passphrase = match passphrase {
+        Some(p) => p,
+        None => {
+            owned = prompt_new_passphrase()?;
+            &owned
+        }
+    };
+    let blob = crypto::encrypt(passphrase, secrets.join("\n").as_bytes())?;
+    fs::write(path, blob)
 }
 
-fn parse_totp(uri: &str) -> Option<(String, String, TOTP)> {
+pub(crate) fn parse_totp(uri: &str) -> Option<(String, String, TOTP, OtpKind)> {
     let url = Url::parse(uri).ok()?;
-    
-    // Extract account name from path (after /totp/)
+    let is_hotp = url.host_str() == Some("hotp");
+
+    // Extract account name from path (after /totp/ or /hotp/)
     let path = url.path();
     let label = urlencoding::decode(path.trim_start_matches("/totp/").trim_start_matches('/')).ok()?;
-    
+
     // Label can be "issuer:account" or just "account"
     let (issuer_from_label, account) = if let Some(pos) = label.find(':') {
         (Some(label[..pos].to_string()), label[pos + 1..].to_string())
@@ -71,7 +165,8 @@ fn parse_totp(uri: &str) -> Option<(String, String, TOTP)> {
     let mut algorithm = Algorithm::SHA1;
     let mut digits = 6u32;
     let mut period = 30u64;
-    
+    let mut counter = 0u64;
+
     for (key, value) in url.query_pairs() {
         match key.as_ref() {
             "secret" => secret = Some(value.to_string()),
@@ -83,17 +178,29 @@ fn parse_totp(uri: &str) -> Option<(String, String, TOTP)> {
                     _ => Algorithm::SHA1,
                 };
             }
-            "digits" => digits = value.parse().unwrap_or(6),
-            "period" => period = value.parse().unwrap_or(30),
+            "digits" => digits = value.parse::<u32>().ok().filter(|d| (6..=8).contains(d)).unwrap_or(6),
+            "period" => period = value.parse::<u64>().ok().filter(|&p| p > 0).unwrap_or(30),
+            "counter" => counter = value.parse().unwrap_or(0),
             _ => {}
         }
     }
-    
+
+    let kind = if is_hotp {
+        OtpKind::Hotp { counter }
+    } else {
+        let is_steam = url.host_str() == Some("steam")
+            || issuer
+                .as_deref()
+                .map(|i| i.eq_ignore_ascii_case("steam"))
+                .unwrap_or(false);
+        OtpKind::Totp { is_steam }
+    };
+
     let secret = secret?;
     let secret_bytes = BASE32_NOPAD
         .decode(secret.to_uppercase().as_bytes())
         .ok()?;
-    
+
     let totp = TOTP::new_unchecked(
         algorithm,
         digits as usize,
@@ -103,62 +210,99 @@ fn parse_totp(uri: &str) -> Option<(String, String, TOTP)> {
         Some(issuer.clone().unwrap_or_default()),
         account.clone(),
     );
-    
-    Some((account, issuer.unwrap_or_default(), totp))
-}
 
-fn generate_code(totp: &TOTP) -> String {
-    let time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    totp.generate(time)
+    Some((account, issuer.unwrap_or_default(), totp, kind))
 }
 
-fn seconds_remaining() -> u64 {
-    let time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    30 - (time % 30)
+pub(crate) fn generate_code(totp: &TOTP, kind: OtpKind) -> String {
+    match kind {
+        OtpKind::Totp { is_steam: true } => {
+            let time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            steam_code(&totp.secret, totp.step, time)
+        }
+        OtpKind::Totp { is_steam: false } => {
+            let time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+            totp.generate(time)
+        }
+        OtpKind::Hotp { counter } => hotp_code(&totp.secret, totp.algorithm, totp.digits, counter),
+    }
 }
 
-fn display(secrets: &[String], line_count: usize) {
-    // Move cursor up to redraw
-    if line_count > 0 {
-        print!("\x1B[{}A", line_count);
+/// Steam Guard's non-standard TOTP variant: a 5-character alphanumeric code
+/// derived from the same HMAC-SHA1 counter as regular TOTP, but truncated
+/// and mapped through `STEAM_ALPHABET` instead of rendered as decimal digits.
+fn steam_code(secret: &[u8], period: u64, time: u64) -> String {
+    type HmacSha1 = Hmac<Sha1>;
+
+    let counter = time / period;
+    let mut mac = HmacSha1::new_from_slice(secret).expect("HMAC accepts a key of any size");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    let offset = (hash[hash.len() - 1] & 0x0F) as usize;
+    let bytes: [u8; 4] = hash[offset..offset + 4].try_into().unwrap();
+    let mut full = u32::from_be_bytes(bytes) & 0x7FFF_FFFF;
+
+    let mut code = String::with_capacity(5);
+    for _ in 0..5 {
+        code.push(STEAM_ALPHABET[(full % 26) as usize] as char);
+        full /= 26;
     }
-    
-    let remaining = seconds_remaining();
-    println!("{:<30} {:<20} {:>8} {:>4}", "USERNAME", "ISSUER", "CODE", "TTL");
-    println!("{}", "-".repeat(68));
-    
-    for uri in secrets {
-        if let Some((account, issuer, totp)) = parse_totp(uri) {
-            let code = generate_code(&totp);
-            println!("{:<30} {:<20} {:>8} {:>3}s", account, issuer, code, remaining);
+    code
+}
+
+/// RFC 4226 HOTP: HMAC over the big-endian counter, dynamic truncation, then
+/// reduced to `digits` decimal digits.
+fn hotp_code(secret: &[u8], algorithm: Algorithm, digits: usize, counter: u64) -> String {
+    let hash = match algorithm {
+        Algorithm::SHA1 => {
+            let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts a key of any size");
+            mac.update(&counter.to_be_bytes());
+            mac.finalize().into_bytes().to_vec()
         }
-    }
-    io::stdout().flush().unwrap();
+        Algorithm::SHA256 => {
+            let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts a key of any size");
+            mac.update(&counter.to_be_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+        Algorithm::SHA512 => {
+            let mut mac = Hmac::<Sha512>::new_from_slice(secret).expect("HMAC accepts a key of any size");
+            mac.update(&counter.to_be_bytes());
+            mac.finalize().into_bytes().to_vec()
+        }
+    };
+
+    let offset = (hash[hash.len() - 1] & 0x0F) as usize;
+    let bytes: [u8; 4] = hash[offset..offset + 4].try_into().unwrap();
+    let code = (u32::from_be_bytes(bytes) & 0x7FFF_FFFF) as u64;
+    let modulus = 10u64.pow(digits as u32);
+    format!("{:0width$}", code % modulus, width = digits)
 }
 
-fn run_tui(secrets_path: &str) {
-    let secrets = load_secrets(secrets_path);
-    
-    if secrets.is_empty() {
-        eprintln!("No secrets found. Import some with: auth-tui import <file>");
-        return;
-    }
-    
-    // header (2) + entries
-    let line_count = 2 + secrets.len();
-    let mut first = true;
-    
-    loop {
-        display(&secrets, if first { 0 } else { line_count });
-        first = false;
-        std::thread::sleep(Duration::from_secs(1));
+/// Rewrite the `counter` query parameter of a stored HOTP URI, leaving
+/// everything else untouched. Used by the TUI's reveal/advance action.
+pub(crate) fn bump_hotp_counter(uri: &str, new_counter: u64) -> Option<String> {
+    let mut url = Url::parse(uri).ok()?;
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    let had_counter = pairs.iter().any(|(key, _)| key == "counter");
+    {
+        let mut qp = url.query_pairs_mut();
+        qp.clear();
+        for (key, value) in pairs {
+            if key == "counter" {
+                qp.append_pair("counter", &new_counter.to_string());
+            } else {
+                qp.append_pair(&key, &value);
+            }
+        }
+        if !had_counter {
+            qp.append_pair("counter", &new_counter.to_string());
+        }
     }
+    Some(url.to_string())
 }
 
 fn main() {
@@ -167,32 +311,106 @@ fn main() {
 
     match cli.command {
         Some(Command::Import { path }) => {
-            let mut existing = load_secrets(&secrets_path);
+            let (mut existing, passphrase) = load_secrets_store(&secrets_path);
             let new_secrets = load_secrets(&path);
             let count = new_secrets.len();
-            
+
             for s in new_secrets {
                 if !existing.contains(&s) {
                     existing.push(s);
                 }
             }
-            
-            if let Err(e) = save_secrets(&secrets_path, &existing) {
+
+            if let Err(e) = save_secrets(&secrets_path, &existing, passphrase.as_deref()) {
                 eprintln!("Failed to save: {}", e);
                 std::process::exit(1);
             }
             println!("Imported {} entries", count);
         }
         Some(Command::Export { path }) => {
-            let secrets = load_secrets(&secrets_path);
+            let (secrets, _passphrase) = load_secrets_store(&secrets_path);
             if let Err(e) = fs::write(&path, secrets.join("\n")) {
                 eprintln!("Failed to export: {}", e);
                 std::process::exit(1);
             }
             println!("Exported {} entries to {}", secrets.len(), path);
         }
+        Some(Command::Migrate) => {
+            let data = fs::read(&secrets_path).unwrap_or_default();
+            if crypto::is_encrypted(&data) {
+                eprintln!("{} is already encrypted", secrets_path);
+                std::process::exit(1);
+            }
+            let secrets = load_secrets(&secrets_path);
+            if let Err(e) = save_secrets(&secrets_path, &secrets, None) {
+                eprintln!("Failed to encrypt: {}", e);
+                std::process::exit(1);
+            }
+            println!("Encrypted {} ({} entries)", secrets_path, secrets.len());
+        }
+        Some(Command::Qr { selector }) => {
+            let (secrets, _passphrase) = load_secrets_store(&secrets_path);
+            let uri = match qr::find_entry(&secrets, &selector) {
+                Some(uri) => uri,
+                None => {
+                    eprintln!("No entry matching '{}'", selector);
+                    std::process::exit(1);
+                }
+            };
+            match qr::render(uri) {
+                Ok(art) => print!("{}", art),
+                Err(e) => {
+                    eprintln!("Failed to render QR code: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Scan { image }) => {
+            let uri = match qr::scan(&image) {
+                Ok(uri) => uri,
+                Err(e) => {
+                    eprintln!("Failed to scan QR code: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let (mut existing, passphrase) = load_secrets_store(&secrets_path);
+            if existing.contains(&uri) {
+                println!("Entry already present");
+                return;
+            }
+            existing.push(uri);
+            if let Err(e) = save_secrets(&secrets_path, &existing, passphrase.as_deref()) {
+                eprintln!("Failed to save: {}", e);
+                std::process::exit(1);
+            }
+            println!("Imported 1 entry");
+        }
+        Some(Command::Add) => {
+            let uri = match add::run() {
+                Ok(uri) => uri,
+                Err(e) => {
+                    eprintln!("Failed to add entry: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let (mut existing, passphrase) = load_secrets_store(&secrets_path);
+            existing.push(uri);
+            if let Err(e) = save_secrets(&secrets_path, &existing, passphrase.as_deref()) {
+                eprintln!("Failed to save: {}", e);
+                std::process::exit(1);
+            }
+            println!("Added entry to {}", secrets_path);
+        }
         None => {
-            run_tui(&secrets_path);
+            let (secrets, passphrase) = load_secrets_store(&secrets_path);
+            if secrets.is_empty() {
+                eprintln!("No secrets found. Import some with: auth-tui import <file>");
+                return;
+            }
+            if let Err(e) = tui::run(&secrets_path, secrets, passphrase) {
+                eprintln!("TUI error: {}", e);
+                std::process::exit(1);
+            }
         }
     }
 }
@@ -0,0 +1,128 @@
+//! QR code provisioning: render a stored entry as a scannable `otpauth://`
+//! QR code, and decode one back from an image for cross-device setup.
+
+use data_encoding::BASE32_NOPAD;
+use qrcode::{Color as QrColor, QrCode};
+use std::io;
+use totp_rs::Algorithm;
+
+use crate::{parse_totp, OtpKind};
+
+/// Find a stored entry by 1-based index, or by a case-insensitive substring
+/// match against its issuer/account.
+pub fn find_entry<'a>(secrets: &'a [String], selector: &str) -> Option<&'a String> {
+    if let Ok(idx) = selector.parse::<usize>() {
+        if idx >= 1 {
+            return secrets.get(idx - 1);
+        }
+    }
+    let needle = selector.to_lowercase();
+    secrets.iter().find(|uri| {
+        parse_totp(uri)
+            .map(|(account, issuer, _, _)| {
+                account.to_lowercase().contains(&needle) || issuer.to_lowercase().contains(&needle)
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Reconstruct the canonical `otpauth://totp/...` (or `otpauth://hotp/...`,
+/// or `otpauth://steam/...` for a Steam entry) URL for a stored entry.
+fn canonical_uri(uri: &str) -> Option<String> {
+    let (account, issuer, totp, kind) = parse_totp(uri)?;
+    let algorithm = match totp.algorithm {
+        Algorithm::SHA1 => "SHA1",
+        Algorithm::SHA256 => "SHA256",
+        Algorithm::SHA512 => "SHA512",
+    };
+    let secret = BASE32_NOPAD.encode(&totp.secret);
+    let label = if issuer.is_empty() {
+        account
+    } else {
+        format!("{}:{}", issuer, account)
+    };
+
+    let counter_or_period = match kind {
+        OtpKind::Hotp { counter } => format!("counter={}", counter),
+        OtpKind::Totp { .. } => format!("period={}", totp.step),
+    };
+    let host = match kind {
+        OtpKind::Hotp { .. } => "hotp",
+        OtpKind::Totp { is_steam: true } => "steam",
+        OtpKind::Totp { is_steam: false } => "totp",
+    };
+    // Steam entries detected via the `steam` host have no `issuer=Steam`
+    // text. Besides keeping the host above, give them `issuer=Steam` so a
+    // rescan still parses back as Steam rather than as a plain TOTP.
+    let issuer = if matches!(kind, OtpKind::Totp { is_steam: true }) && issuer.is_empty() {
+        "Steam".to_string()
+    } else {
+        issuer
+    };
+
+    Some(format!(
+        "otpauth://{}/{}?secret={}&issuer={}&algorithm={}&digits={}&{}",
+        host,
+        urlencoding::encode(&label),
+        secret,
+        urlencoding::encode(&issuer),
+        algorithm,
+        totp.digits,
+        counter_or_period,
+    ))
+}
+
+/// Render a stored entry as a QR code using UTF-8 half-block glyphs, two
+/// pixel rows per line of text.
+pub fn render(uri: &str) -> io::Result<String> {
+    let canonical =
+        canonical_uri(uri).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not a valid TOTP entry"))?;
+    let code = QrCode::new(canonical.as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    Ok(render_unicode(&code))
+}
+
+fn render_unicode(code: &QrCode) -> String {
+    let width = code.width();
+    let colors = code.to_colors();
+    let is_dark = |x: usize, y: usize| y < width && colors[y * width + x] == QrColor::Dark;
+
+    let mut out = String::new();
+    let mut y = 0;
+    while y < width {
+        for x in 0..width {
+            out.push(match (is_dark(x, y), is_dark(x, y + 1)) {
+                (true, true) => '\u{2588}',  // full block
+                (true, false) => '\u{2580}', // upper half block
+                (false, true) => '\u{2584}', // lower half block
+                (false, false) => ' ',
+            });
+        }
+        out.push('\n');
+        y += 2;
+    }
+    out
+}
+
+/// Decode a QR code image back into its `otpauth://` URI.
+pub fn scan(path: &str) -> io::Result<String> {
+    let img = image::open(path)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?
+        .to_luma8();
+    let mut prepared = rqrr::PreparedImage::prepare(img);
+    let grids = prepared.detect_grids();
+    let grid = grids
+        .first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no QR code found in image"))?;
+    let (_meta, content) = grid
+        .decode()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    if !content.starts_with("otpauth://") {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "QR code does not contain an otpauth:// URI",
+        ));
+    }
+    Ok(content)
+}
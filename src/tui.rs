@@ -0,0 +1,311 @@
+//! Interactive raw-mode TUI: navigate entries, fuzzy-filter, copy a code to
+//! the clipboard, delete an entry, or reveal/advance an HOTP counter.
+//! Replaces the old fixed reprint loop.
+
+use arboard::Clipboard;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::{Color, ResetColor, SetForegroundColor};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{self, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use totp_rs::TOTP;
+
+use crate::{bump_hotp_counter, generate_code, parse_totp, save_secrets, OtpKind};
+
+const PERIOD: u64 = 30;
+
+struct Entry {
+    uri: String,
+    account: String,
+    issuer: String,
+    totp: TOTP,
+    kind: OtpKind,
+    /// Last revealed code for an HOTP entry; cleared on the next period
+    /// tick so a stale code is never shown as current indefinitely.
+    revealed: Option<String>,
+}
+
+enum Mode {
+    Normal,
+    Search,
+}
+
+/// Run the interactive TUI over `secrets` (raw `otpauth://` URIs already
+/// loaded and decrypted). `passphrase` is reused for re-encrypting the store
+/// on delete or HOTP advance; the caller holds the secrets only in memory.
+pub fn run(secrets_path: &str, secrets: Vec<String>, passphrase: Option<String>) -> io::Result<()> {
+    let mut entries: Vec<Entry> = secrets
+        .iter()
+        .filter_map(|uri| {
+            parse_totp(uri).map(|(account, issuer, totp, kind)| Entry {
+                uri: uri.clone(),
+                account,
+                issuer,
+                totp,
+                kind,
+                revealed: None,
+            })
+        })
+        .collect();
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = event_loop(&mut stdout, secrets_path, &mut entries, passphrase);
+
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn entry_code(entry: &Entry) -> String {
+    match entry.kind {
+        OtpKind::Hotp { counter } => entry
+            .revealed
+            .clone()
+            .unwrap_or_else(|| format!("(r to reveal #{})", counter)),
+        OtpKind::Totp { .. } => generate_code(&entry.totp, entry.kind),
+    }
+}
+
+fn entry_ttl(entry: &Entry, now: u64) -> String {
+    match entry.kind {
+        OtpKind::Totp { .. } => {
+            let step = entry.totp.step.max(1);
+            format!("{:>3}s", step - (now % step))
+        }
+        OtpKind::Hotp { counter } => format!("HOTP #{}", counter),
+    }
+}
+
+fn event_loop(
+    stdout: &mut io::Stdout,
+    secrets_path: &str,
+    entries: &mut Vec<Entry>,
+    passphrase: Option<String>,
+) -> io::Result<()> {
+    let mut mode = Mode::Normal;
+    let mut filter = String::new();
+    let mut selected = 0usize;
+    let mut last_revealed_tick = now_secs() / PERIOD;
+    let mut status: Option<String> = None;
+
+    loop {
+        let visible = visible_indices(entries, &filter);
+        if !visible.is_empty() && selected >= visible.len() {
+            selected = visible.len() - 1;
+        }
+
+        let now = now_secs();
+        let tick = now / PERIOD;
+        if tick != last_revealed_tick {
+            for e in entries.iter_mut() {
+                e.revealed = None;
+            }
+            last_revealed_tick = tick;
+        }
+        // Recomputed every frame (not cached on a single global tick) so an
+        // entry with a non-30s period never has its code or TTL lag behind
+        // what totp-rs would compute right now.
+        let codes: Vec<String> = entries.iter().map(entry_code).collect();
+
+        render(stdout, entries, &codes, &visible, selected, now, &mode, &filter, &status)?;
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        if !event::poll(Duration::from_millis(200))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        status = None;
+
+        match mode {
+            Mode::Search => match key.code {
+                KeyCode::Esc => {
+                    filter.clear();
+                    mode = Mode::Normal;
+                }
+                KeyCode::Enter => mode = Mode::Normal,
+                KeyCode::Backspace => {
+                    filter.pop();
+                }
+                KeyCode::Char(c) => {
+                    filter.push(c);
+                    selected = 0;
+                }
+                _ => {}
+            },
+            Mode::Normal => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => {
+                    selected = selected.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') if selected + 1 < visible.len() => {
+                    selected += 1;
+                }
+                KeyCode::Char('/') => mode = Mode::Search,
+                KeyCode::Enter | KeyCode::Char('c') => {
+                    if let Some(&idx) = visible.get(selected) {
+                        status = Some(match copy_to_clipboard(&codes[idx]) {
+                            Ok(()) => format!("Copied code for {}", entries[idx].account),
+                            Err(e) => format!("Clipboard error: {}", e),
+                        });
+                    }
+                }
+                KeyCode::Char('r') => {
+                    if let Some(&idx) = visible.get(selected) {
+                        status = Some(reveal_and_advance(secrets_path, entries, idx, passphrase.as_deref()));
+                    }
+                }
+                KeyCode::Char('d') => {
+                    if let Some(&idx) = visible.get(selected) {
+                        let account = entries[idx].account.clone();
+                        entries.remove(idx);
+                        let uris: Vec<String> = entries.iter().map(|e| e.uri.clone()).collect();
+                        status = Some(match save_secrets(secrets_path, &uris, passphrase.as_deref()) {
+                            Ok(()) => format!("Deleted {}", account),
+                            Err(e) => format!("Failed to save: {}", e),
+                        });
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Reveal the current HOTP code for `entries[idx]`, then advance its counter
+/// and persist the bumped URI. A no-op (with a status message) for TOTP
+/// entries, since only HOTP counters need to move on use.
+fn reveal_and_advance(secrets_path: &str, entries: &mut [Entry], idx: usize, passphrase: Option<&str>) -> String {
+    let OtpKind::Hotp { counter } = entries[idx].kind else {
+        return "Not an HOTP entry".to_string();
+    };
+
+    let code = generate_code(&entries[idx].totp, entries[idx].kind);
+    let new_counter = counter + 1;
+    let Some(new_uri) = bump_hotp_counter(&entries[idx].uri, new_counter) else {
+        return "Failed to advance counter: malformed URI".to_string();
+    };
+
+    entries[idx].uri = new_uri;
+    entries[idx].kind = OtpKind::Hotp { counter: new_counter };
+    entries[idx].revealed = Some(code.clone());
+
+    let uris: Vec<String> = entries.iter().map(|e| e.uri.clone()).collect();
+    match save_secrets(secrets_path, &uris, passphrase) {
+        Ok(()) => format!("{} -> counter {}", code, new_counter),
+        Err(e) => format!("Revealed {} but failed to save: {}", code, e),
+    }
+}
+
+fn copy_to_clipboard(code: &str) -> Result<(), String> {
+    let mut clipboard = Clipboard::new().map_err(|e| e.to_string())?;
+    clipboard.set_text(code.to_string()).map_err(|e| e.to_string())
+}
+
+fn visible_indices(entries: &[Entry], filter: &str) -> Vec<usize> {
+    if filter.is_empty() {
+        return (0..entries.len()).collect();
+    }
+    let needle = filter.to_lowercase();
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.account.to_lowercase().contains(&needle) || e.issuer.to_lowercase().contains(&needle))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn progress_bar(remaining: u64, period: u64, width: usize) -> String {
+    let filled = ((remaining as f64 / period.max(1) as f64) * width as f64).round() as usize;
+    let filled = filled.min(width);
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(width - filled))
+}
+
+/// The period/remaining-seconds pair the bottom progress bar tracks: the
+/// highlighted entry's own TOTP step, so the bar stays meaningful for
+/// entries with a non-30s period, falling back to the default period when
+/// nothing is selected.
+fn selected_countdown(entries: &[Entry], visible: &[usize], selected: usize, now: u64) -> (u64, u64) {
+    let period = visible
+        .get(selected)
+        .map(|&idx| entries[idx].totp.step.max(1))
+        .unwrap_or(PERIOD);
+    (period, period - (now % period))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render(
+    stdout: &mut io::Stdout,
+    entries: &[Entry],
+    codes: &[String],
+    visible: &[usize],
+    selected: usize,
+    now: u64,
+    mode: &Mode,
+    filter: &str,
+    status: &Option<String>,
+) -> io::Result<()> {
+    queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(terminal::ClearType::All))?;
+
+    if entries.is_empty() {
+        write!(stdout, "No secrets found. Import some with: auth-tui import <file>\r\n")?;
+        stdout.flush()?;
+        return Ok(());
+    }
+
+    write!(stdout, "{:<30} {:<20} {:>16} {:>10}\r\n", "ACCOUNT", "ISSUER", "CODE", "TTL")?;
+    write!(stdout, "{}\r\n", "-".repeat(80))?;
+
+    for (row, &idx) in visible.iter().enumerate() {
+        let e = &entries[idx];
+        let marker = if row == selected { ">" } else { " " };
+        if row == selected {
+            queue!(stdout, SetForegroundColor(Color::Green))?;
+        }
+        write!(
+            stdout,
+            "{} {:<28} {:<20} {:>16} {:>10}\r\n",
+            marker,
+            e.account,
+            e.issuer,
+            codes[idx],
+            entry_ttl(e, now)
+        )?;
+        if row == selected {
+            queue!(stdout, ResetColor)?;
+        }
+    }
+
+    let (period, remaining) = selected_countdown(entries, visible, selected, now);
+    write!(stdout, "\r\n{} {:>2}s\r\n", progress_bar(remaining, period, 30), remaining)?;
+
+    match mode {
+        Mode::Search => write!(stdout, "/{}\r\n", filter)?,
+        Mode::Normal if !filter.is_empty() => write!(stdout, "filter: {}\r\n", filter)?,
+        Mode::Normal => write!(stdout, "\r\n")?,
+    }
+
+    if let Some(s) = status {
+        write!(stdout, "{}\r\n", s)?;
+    }
+
+    write!(
+        stdout,
+        "\r\n\u{2191}/k \u{2193}/j move  / search  Enter/c copy  r reveal/advance HOTP  d delete  q quit\r\n"
+    )?;
+    stdout.flush()
+}